@@ -9,13 +9,41 @@
 //! [`embedded-hal`]: https://docs.rs/embedded-hal/~0.2
 
 #![deny(missing_docs)]
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 
 use embedded_hal_async::delay::DelayUs;
 use embedded_hal_async::i2c::I2c;
 
 const I2C_ADDRESS: u8 = 0x38;
 
+/// Minimum poll interval enforced by [`AHT10::read`] by default, in
+/// microseconds. This matches the 2000 ms default used by the Linux hwmon
+/// driver to avoid self-heating the die.
+const DEFAULT_MIN_POLL_INTERVAL_US: u32 = 2_000_000;
+
+/// Default maximum number of busy-flag poll attempts, used by `new` and
+/// until changed with [`AHT10::set_max_poll_attempts`].
+const DEFAULT_MAX_POLL_ATTEMPTS: u8 = 8;
+
+/// Delay before the first busy-flag poll after issuing a command, in
+/// milliseconds. Temperature is often ready almost immediately, but this
+/// gives the sensor a head start before we start spending time on the bus.
+const INITIAL_POLL_DELAY_MS: u32 = 80;
+
+/// Delay between subsequent busy-flag polls, in milliseconds.
+const POLL_RETRY_DELAY_MS: u32 = 30;
+
+/// A source of monotonic time, used by [`AHT10::read`] to enforce the
+/// minimum poll interval.
+///
+/// Since this crate is `no_std`, callers must supply their own clock (e.g. a
+/// hardware timer or RTC) by implementing this trait.
+pub trait Clock {
+    /// Returns a timestamp in microseconds. The only requirement is that it
+    /// increases monotonically for the lifetime of the clock.
+    fn now_us(&mut self) -> u64;
+}
+
 #[derive(Copy, Clone)]
 #[repr(u8)]
 enum Command {
@@ -45,6 +73,10 @@ bitflags! {
 pub enum Error<E> {
     /// Device is not calibrated
     Uncalibrated(),
+    /// The CRC byte appended to the measurement did not match the computed checksum.
+    ChecksumMismatch(),
+    /// The sensor was still busy after the maximum number of poll attempts.
+    Timeout(),
     /// Underlying bus error.
     BusError(E),
 }
@@ -55,13 +87,44 @@ impl<E> core::convert::From<E> for Error<E> {
     }
 }
 
+/// Computes the CRC8 checksum (polynomial 0x31, init 0xFF) that AHT20-class
+/// chips append to the measurement response.
+fn crc8(data: &[u8]) -> u8 {
+    let mut crc: u8 = 0xff;
+    for &byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            if crc & 0x80 != 0 {
+                crc = (crc << 1) ^ 0x31;
+            } else {
+                crc <<= 1;
+            }
+        }
+    }
+    crc
+}
+
 /// AHT10 driver
-pub struct AHT10<I2C, D> {
+pub struct AHT10<I2C> {
     i2c: I2C,
-    delay: D,
+    /// Whether to verify the trailing CRC byte that AHT20-class chips append
+    /// to the measurement response. The AHT10 does not emit this byte.
+    verify_crc: bool,
+    /// Minimum time between measurements, in microseconds, enforced by `read`
+    /// to protect against self-heating.
+    min_poll_interval_us: u32,
+    /// The most recent successful measurement, along with the timestamp (in
+    /// microseconds, per the injected [`Clock`]) it was taken at.
+    last_reading: Option<(u64, Measurement)>,
+    /// Maximum number of times to poll the status register for the busy
+    /// flag to clear before giving up with [`Error::Timeout`].
+    max_poll_attempts: u8,
+    /// The measurement mode last selected via `set_mode`.
+    mode: Mode,
 }
 
 /// Humidity reading from AHT10.
+#[derive(Copy, Clone)]
 pub struct Humidity {
     h: u32,
 }
@@ -77,6 +140,7 @@ impl Humidity {
 }
 
 /// Temperature reading from AHT10.
+#[derive(Copy, Clone)]
 pub struct Temperature {
     t: u32,
 }
@@ -91,50 +155,298 @@ impl Temperature {
     }
 }
 
-impl<I2C, D> AHT10<I2C, D>
+/// A combined humidity and temperature measurement, as returned by
+/// [`AHT10::read`].
+#[derive(Copy, Clone)]
+pub struct Measurement {
+    humidity: Humidity,
+    temperature: Temperature,
+}
+
+impl Measurement {
+    /// The humidity component of this measurement.
+    pub fn humidity(&self) -> Humidity {
+        self.humidity
+    }
+
+    /// The temperature component of this measurement.
+    pub fn temperature(&self) -> Temperature {
+        self.temperature
+    }
+
+    /// Dew point, in degrees Celsius, computed from the humidity and
+    /// temperature via the Magnus formula.
+    ///
+    /// A reading of 0% relative humidity is floored to a tiny positive value
+    /// rather than fed to [`ln`] directly, since `ln` requires `x > 0`.
+    pub fn dew_point(&self) -> f32 {
+        let rh = positive_or_min(self.humidity.rh());
+        let t = self.temperature.celsius();
+        let gamma = ln(rh / 100.0) + (17.62 * t) / (243.12 + t);
+        243.12 * gamma / (17.62 - gamma)
+    }
+
+    /// Absolute humidity, in grams of water vapor per cubic meter of air.
+    pub fn absolute_humidity(&self) -> f32 {
+        let rh = positive_or_min(self.humidity.rh());
+        let t = self.temperature.celsius();
+        let saturation_vapor_pressure = 6.112 * exp((17.67 * t) / (t + 243.5));
+        (saturation_vapor_pressure * rh * 2.1674) / (273.15 + t)
+    }
+}
+
+/// Floors `x` to [`f32::MIN_POSITIVE`] if it isn't already a positive
+/// number, for passing a possibly-zero relative humidity to [`ln`].
+fn positive_or_min(x: f32) -> f32 {
+    if x > 0.0 {
+        x
+    } else {
+        f32::MIN_POSITIVE
+    }
+}
+
+/// Natural logarithm of `x`, for `x > 0`.
+///
+/// `core` has no transcendental functions (they normally come from `std` or
+/// a `libm`-style crate), and this driver is small enough not to want either
+/// dependency just for [`Measurement::dew_point`]. This extracts the base-2
+/// exponent from the float's bit pattern and refines the mantissa with the
+/// series `ln(m) = 2*atanh((m-1)/(m+1))`, which converges quickly for `m` in
+/// `[1, 2)`.
+fn ln(x: f32) -> f32 {
+    if x < f32::MIN_POSITIVE {
+        // Subnormal: scale by 2^23 into the normal range first.
+        return ln(x * 8_388_608.0) - 23.0 * core::f32::consts::LN_2;
+    }
+    let bits = x.to_bits();
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127;
+    let mantissa = f32::from_bits((bits & 0x007f_ffff) | (127 << 23));
+    let y = (mantissa - 1.0) / (mantissa + 1.0);
+    let y2 = y * y;
+    let mut term = y;
+    let mut sum = term;
+    for k in 1..6 {
+        term *= y2;
+        sum += term / (2 * k + 1) as f32;
+    }
+    (exponent as f32) * core::f32::consts::LN_2 + 2.0 * sum
+}
+
+/// `e` raised to the power `x`, via range reduction to `x = k*ln(2) + r`
+/// followed by a Taylor series for `e^r`. See [`ln`] for why this is
+/// hand-rolled instead of using `std` or a math crate.
+fn exp(x: f32) -> f32 {
+    // `f32::round` lives in `std` (it's backed by a libm call), so the
+    // nearest integer is rounded by hand here: bias by half a unit in the
+    // direction of `x` before the truncating `as i32` cast.
+    let units = x / core::f32::consts::LN_2;
+    let k = if units >= 0.0 {
+        (units + 0.5) as i32
+    } else {
+        (units - 0.5) as i32
+    };
+    let r = x - (k as f32) * core::f32::consts::LN_2;
+    let mut term = 1.0f32;
+    let mut sum = 1.0f32;
+    for n in 1..8 {
+        term *= r / n as f32;
+        sum += term;
+    }
+    let scale = f32::from_bits(((k + 127) as u32) << 23);
+    sum * scale
+}
+
+/// Measurement mode, corresponding to the status register's `MODE` bits.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// Command mode: the sensor only measures when triggered, which is how
+    /// this driver operates by default.
+    Normal,
+    /// Cyclic mode: the sensor samples continuously in the background, so
+    /// `read` just needs to fetch the latest result rather than trigger and
+    /// wait for a fresh conversion.
+    Cyclic,
+}
+
+/// Bits of the status register's `MODE` field (see [`StatusFlags::MODE`])
+/// that indicate cyclic mode.
+const MODE_CYC_BITS: u8 = 1 << 5;
+
+impl<I2C> AHT10<I2C>
 where
     I2C: I2c,
-    D: DelayUs,
 {
     /// Creates a new AHT10 device from an I2C peripheral.
-    pub async fn new(i2c: I2C, delay: D) -> Result<Self, I2C::Error> {
+    pub async fn new(i2c: I2C, delay: &mut impl DelayUs) -> Result<Self, Error<I2C::Error>> {
+        Self::new_impl(i2c, delay, false).await
+    }
+
+    /// Creates a new device from an I2C peripheral, verifying the CRC8 byte
+    /// that AHT20-class chips append to each measurement response.
+    ///
+    /// Use this instead of [`new`](Self::new) when talking to an AHT20 (or
+    /// compatible) sensor; plain AHT10s do not emit a CRC byte and will
+    /// always fail the check.
+    pub async fn new_with_crc(
+        i2c: I2C,
+        delay: &mut impl DelayUs,
+    ) -> Result<Self, Error<I2C::Error>> {
+        Self::new_impl(i2c, delay, true).await
+    }
+
+    async fn new_impl(
+        i2c: I2C,
+        delay: &mut impl DelayUs,
+        verify_crc: bool,
+    ) -> Result<Self, Error<I2C::Error>> {
         let mut dev = AHT10 {
-            i2c: i2c,
-            delay: delay,
+            i2c,
+            verify_crc,
+            min_poll_interval_us: DEFAULT_MIN_POLL_INTERVAL_US,
+            last_reading: None,
+            max_poll_attempts: DEFAULT_MAX_POLL_ATTEMPTS,
+            mode: Mode::Normal,
         };
         dev.write_cmd(Command::GetRaw, 0).await?;
-        dev.delay.delay_ms(300).await;
+        dev.wait_until_ready(delay).await?;
         // MSB notes:
         // Bit 2 set => temperature is roughly doubled(?)
         // Bit 3 set => calibrated flag
-        // Bit 4 => temperature is negative? (cyc mode?)
+        // Bit 4 set => cyclic mode, see `set_mode`
         dev.write_cmd(Command::Calibrate, 0x0800).await?;
-        dev.delay.delay_ms(300).await;
+        dev.wait_until_ready(delay).await?;
         Ok(dev)
     }
 
     /// Soft reset the sensor.
-    pub async fn reset(&mut self) -> Result<(), I2C::Error> {
+    pub async fn reset(&mut self, delay: &mut impl DelayUs) -> Result<(), I2C::Error> {
         self.write_cmd(Command::Reset, 0).await?;
-        self.delay.delay_ms(20).await;
+        delay.delay_ms(20).await;
         Ok(())
     }
 
+    /// Sets the minimum time between measurements, in milliseconds. A `read`
+    /// requested before this interval has elapsed since the previous one
+    /// returns the cached measurement instead of polling the sensor again,
+    /// which protects the die from self-heating. Defaults to 2000 ms.
+    pub fn set_min_poll_interval(&mut self, ms: u32) {
+        self.min_poll_interval_us = ms.saturating_mul(1000);
+    }
+
+    /// Returns the most recent measurement, if one has been taken, without
+    /// polling the sensor.
+    pub fn last_reading(&self) -> Option<Measurement> {
+        self.last_reading.map(|(_, measurement)| measurement)
+    }
+
+    /// Sets the maximum number of times `read` and `set_mode` will poll the
+    /// status register for the busy flag to clear before giving up with
+    /// [`Error::Timeout`]. Defaults to 8, which is also the limit `new` uses.
+    pub fn set_max_poll_attempts(&mut self, attempts: u8) {
+        self.max_poll_attempts = attempts;
+    }
+
+    /// Configures the sensor's measurement mode.
+    ///
+    /// In [`Mode::Cyclic`], the sensor samples continuously in the
+    /// background; subsequent `read`s can then just fetch the latest result
+    /// rather than trigger a new conversion each time.
+    pub async fn set_mode(
+        &mut self,
+        mode: Mode,
+        delay: &mut impl DelayUs,
+    ) -> Result<(), Error<I2C::Error>> {
+        let dat: u16 = match mode {
+            Mode::Normal => 0x0800,
+            Mode::Cyclic => 0x1800,
+        };
+        self.write_cmd(Command::Calibrate, dat).await?;
+        self.wait_until_ready(delay).await?;
+        self.mode = mode;
+        Ok(())
+    }
+
+    /// Reads back the sensor's current measurement mode from the status
+    /// register.
+    pub async fn mode(&mut self) -> Result<Mode, I2C::Error> {
+        let mut buf = [0u8];
+        self.i2c.read(I2C_ADDRESS, &mut buf).await?;
+        if buf[0] & StatusFlags::MODE.bits() == MODE_CYC_BITS {
+            Ok(Mode::Cyclic)
+        } else {
+            Ok(Mode::Normal)
+        }
+    }
+
     /// Read humidity and temperature.
-    pub async fn read(&mut self) -> Result<(Humidity, Temperature), Error<I2C::Error>> {
+    ///
+    /// If less than the minimum poll interval (see
+    /// [`set_min_poll_interval`](Self::set_min_poll_interval)) has elapsed
+    /// since the last successful measurement, this returns the cached
+    /// reading instead of polling the sensor again.
+    ///
+    /// In [`Mode::Cyclic`] the sensor is already continuously converting in
+    /// the background, so this skips triggering a new conversion and
+    /// waiting for the busy flag to clear, and just fetches the latest
+    /// result.
+    pub async fn read(
+        &mut self,
+        delay: &mut impl DelayUs,
+        clock: &mut impl Clock,
+    ) -> Result<Measurement, Error<I2C::Error>> {
+        let now = clock.now_us();
+        if let Some((last_us, measurement)) = self.last_reading {
+            if now.wrapping_sub(last_us) < self.min_poll_interval_us as u64 {
+                return Ok(measurement);
+            }
+        }
+
+        if self.mode != Mode::Cyclic {
+            // Sort of reverse engineered the cmd data:
+            // Bit 0 -> temperature calibration (0 => +0.5C)
+            // Bit {1,2,3} -> refresh rate? (0 => slow refresh)
+            self.write_cmd(Command::GetCT, 0xff00).await?;
+            self.wait_until_ready(delay).await?;
+        }
+
         let buf: &mut [u8; 7] = &mut [0; 7];
-        // Sort of reverse engineered the cmd data:
-        // Bit 0 -> temperature calibration (0 => +0.5C)
-        // Bit {1,2,3} -> refresh rate? (0 => slow refresh)
-        self.i2c
-            .write_read(I2C_ADDRESS, &[Command::GetCT as u8, 0b11111111, 0], buf).await?;
+        self.i2c.read(I2C_ADDRESS, buf).await?;
         let status = StatusFlags { bits: buf[0] };
         if !status.contains(StatusFlags::CALIBRATION_ENABLE) {
             return Err(Error::Uncalibrated());
         }
+        if self.verify_crc && crc8(&buf[..6]) != buf[6] {
+            return Err(Error::ChecksumMismatch());
+        }
         let hum = ((buf[1] as u32) << 12) | ((buf[2] as u32) << 4) | ((buf[3] as u32) >> 4);
         let temp = (((buf[3] as u32) & 0x0f) << 16) | ((buf[4] as u32) << 8) | (buf[5] as u32);
-        Ok((Humidity { h: hum }, Temperature { t: temp }))
+        let measurement = Measurement {
+            humidity: Humidity { h: hum },
+            temperature: Temperature { t: temp },
+        };
+        self.last_reading = Some((now, measurement));
+        Ok(measurement)
+    }
+
+    /// Waits for the busy flag in the status register to clear, polling
+    /// with a short initial delay followed by bounded, incremental retries.
+    async fn wait_until_ready(
+        &mut self,
+        delay: &mut impl DelayUs,
+    ) -> Result<(), Error<I2C::Error>> {
+        delay.delay_ms(INITIAL_POLL_DELAY_MS).await;
+        for attempt in 0..self.max_poll_attempts {
+            let mut buf = [0u8];
+            self.i2c.read(I2C_ADDRESS, &mut buf).await?;
+            let status = StatusFlags { bits: buf[0] };
+            if !status.contains(StatusFlags::BUSY) {
+                return Ok(());
+            }
+            if attempt + 1 < self.max_poll_attempts {
+                delay.delay_ms(POLL_RETRY_DELAY_MS).await;
+            }
+        }
+        Err(Error::Timeout())
     }
 
     async fn write_cmd(&mut self, cmd: Command, dat: u16) -> Result<(), I2C::Error> {
@@ -144,3 +456,226 @@ where
         ).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32, tol: f32) {
+        assert!(
+            (actual - expected).abs() <= tol,
+            "expected {expected} +/- {tol}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn crc8_known_vectors() {
+        // Values below are computed from the same init/poly (0xff, 0x31)
+        // independently, to check the bit-shuffling in `crc8` against a
+        // reference implementation rather than against itself.
+        assert_eq!(crc8(&[0x00]), 0xac);
+        assert_eq!(crc8(&[0xff]), 0x00);
+        assert_eq!(crc8(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00]), 0x6a);
+    }
+
+    #[test]
+    fn measurement_dew_point_and_absolute_humidity() {
+        // T = 20C, RH = 50%, a commonly cited reference point: dew point
+        // ~9.26C and absolute humidity ~8.64 g/m^3 (e.g. as produced by the
+        // NOAA/NWS online calculators). Raw readings are derived from the
+        // sensor's own encoding rather than hand-picked to make the
+        // formula's unit conversions (rh() as percent, celsius() in C) line
+        // up trivially.
+        let h = ((50.0 / 100.0) * (1u32 << 20) as f32) as u32;
+        let t = (((20.0 + 50.0) / 200.0) * (1u32 << 20) as f32) as u32;
+        let measurement = Measurement {
+            humidity: Humidity { h },
+            temperature: Temperature { t },
+        };
+        assert_close(measurement.dew_point(), 9.26, 0.1);
+        assert_close(measurement.absolute_humidity(), 8.64, 0.1);
+    }
+
+    #[test]
+    fn ln_matches_std() {
+        for x in [0.01f32, 0.1, 0.5, 0.999, 1.0, 1.5, 2.0, 10.0, 1000.0] {
+            assert_close(ln(x), x.ln(), 1e-4 * x.ln().abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn exp_matches_std() {
+        for x in [-10.0f32, -1.5, -0.5, 0.0, 0.5, 1.0, 1.5, 5.0, 10.0] {
+            assert_close(exp(x), x.exp(), 1e-3 * x.exp().max(1e-6));
+        }
+    }
+
+    mod mock_bus {
+        //! A small hand-rolled mock of the I2C bus and delay, exercising
+        //! `AHT10::read` against scripted expectations instead of real
+        //! hardware. `embedded-hal-mock`'s I2C mock is blocking, and this
+        //! driver is built on `embedded-hal-async`, so the mock here
+        //! implements `embedded_hal_async::i2c::I2c` directly (via
+        //! `transaction`, like the blocking mock does) rather than adapting
+        //! a sync one.
+        use super::*;
+        use std::collections::VecDeque;
+
+        pub enum Expect {
+            Write(Vec<u8>),
+            Read(Vec<u8>),
+        }
+
+        pub struct MockI2c {
+            expected: VecDeque<Expect>,
+        }
+
+        impl MockI2c {
+            pub fn new(expected: Vec<Expect>) -> Self {
+                MockI2c {
+                    expected: expected.into(),
+                }
+            }
+
+            pub fn done(&self) {
+                assert!(
+                    self.expected.is_empty(),
+                    "mock i2c had {} unconsumed expectation(s)",
+                    self.expected.len()
+                );
+            }
+        }
+
+        impl embedded_hal_async::i2c::ErrorType for MockI2c {
+            type Error = core::convert::Infallible;
+        }
+
+        impl embedded_hal_async::i2c::I2c for MockI2c {
+            async fn transaction(
+                &mut self,
+                address: u8,
+                operations: &mut [embedded_hal_async::i2c::Operation<'_>],
+            ) -> Result<(), Self::Error> {
+                assert_eq!(address, I2C_ADDRESS, "unexpected device address");
+                for operation in operations {
+                    match (self.expected.pop_front(), operation) {
+                        (
+                            Some(Expect::Write(expected)),
+                            embedded_hal_async::i2c::Operation::Write(actual),
+                        ) => {
+                            assert_eq!(expected.as_slice(), *actual, "unexpected i2c write");
+                        }
+                        (
+                            Some(Expect::Read(response)),
+                            embedded_hal_async::i2c::Operation::Read(buf),
+                        ) => {
+                            buf.copy_from_slice(&response);
+                        }
+                        (Some(_), _) => panic!("i2c operation type mismatch"),
+                        (None, _) => panic!("unexpected i2c operation, no expectations left"),
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        pub struct NoopDelay;
+
+        impl DelayUs for NoopDelay {
+            async fn delay_us(&mut self, _us: u32) {}
+            async fn delay_ms(&mut self, _ms: u32) {}
+        }
+
+        pub struct FixedClock(pub u64);
+
+        impl Clock for FixedClock {
+            fn now_us(&mut self) -> u64 {
+                self.0
+            }
+        }
+
+        /// Polls a future to completion. Every future driven by `MockI2c`/
+        /// `NoopDelay` resolves on its first poll (there's no real I/O to
+        /// wait on), so this never actually needs to wait for a wake-up.
+        pub fn block_on<F: core::future::Future>(fut: F) -> F::Output {
+            use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+            fn clone(_: *const ()) -> RawWaker {
+                RawWaker::new(core::ptr::null(), &VTABLE)
+            }
+            fn noop(_: *const ()) {}
+            static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+            let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+            let mut cx = Context::from_waker(&waker);
+            let mut fut = core::pin::pin!(fut);
+            loop {
+                if let Poll::Ready(value) = fut.as_mut().poll(&mut cx) {
+                    return value;
+                }
+            }
+        }
+
+        fn dev(i2c: MockI2c, mode: Mode, max_poll_attempts: u8, verify_crc: bool) -> AHT10<MockI2c> {
+            AHT10 {
+                i2c,
+                verify_crc,
+                min_poll_interval_us: 0,
+                last_reading: None,
+                max_poll_attempts,
+                mode,
+            }
+        }
+
+        #[test]
+        fn cyclic_mode_skips_trigger_and_poll() {
+            // No write (trigger command) and no status-register poll in the
+            // expectation list: if `read` issued either, the mock would see
+            // the wrong operation type and panic.
+            let i2c = MockI2c::new(vec![Expect::Read(vec![
+                StatusFlags::CALIBRATION_ENABLE.bits(),
+                0x00,
+                0x00,
+                0x00,
+                0x00,
+                0x00,
+                0x00,
+            ])]);
+            let mut device = dev(i2c, Mode::Cyclic, 8, false);
+            let measurement =
+                block_on(device.read(&mut NoopDelay, &mut FixedClock(0))).expect("read failed");
+            assert_eq!(measurement.humidity().raw(), 0);
+            assert_eq!(measurement.temperature().raw(), 0);
+            device.i2c.done();
+        }
+
+        #[test]
+        fn busy_forever_times_out() {
+            let i2c = MockI2c::new(vec![
+                Expect::Write(vec![Command::GetCT as u8, 0xff, 0x00]),
+                Expect::Read(vec![StatusFlags::BUSY.bits()]),
+                Expect::Read(vec![StatusFlags::BUSY.bits()]),
+                Expect::Read(vec![StatusFlags::BUSY.bits()]),
+            ]);
+            let mut device = dev(i2c, Mode::Normal, 3, false);
+            let result = block_on(device.read(&mut NoopDelay, &mut FixedClock(0)));
+            assert!(matches!(result, Err(Error::Timeout())));
+            device.i2c.done();
+        }
+
+        #[test]
+        fn crc_mismatch_is_rejected() {
+            let response = [StatusFlags::CALIBRATION_ENABLE.bits(), 0, 0, 0, 0, 0, 0xff];
+            assert_ne!(crc8(&response[..6]), response[6]);
+            let i2c = MockI2c::new(vec![
+                Expect::Write(vec![Command::GetCT as u8, 0xff, 0x00]),
+                Expect::Read(vec![0x00]),
+                Expect::Read(response.to_vec()),
+            ]);
+            let mut device = dev(i2c, Mode::Normal, 1, true);
+            let result = block_on(device.read(&mut NoopDelay, &mut FixedClock(0)));
+            assert!(matches!(result, Err(Error::ChecksumMismatch())));
+            device.i2c.done();
+        }
+    }
+}